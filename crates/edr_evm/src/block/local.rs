@@ -3,30 +3,65 @@ use std::sync::Arc;
 use edr_eth::{
     block::{self, Header, PartialHeader},
     log::{FilterLog, FullBlockLog, Log, ReceiptLog},
-    receipt::{BlockReceipt, TransactionReceipt, TypedReceipt},
+    receipt::{BlockReceipt, TransactionReceipt, TypedReceipt, TypedReceiptData},
     transaction::{DetailedTransaction, SignedTransaction},
     trie,
     withdrawal::Withdrawal,
-    Address, B256,
+    Address, Bytes, B256,
 };
 use itertools::izip;
+use once_cell::sync::OnceCell;
 use revm::primitives::keccak256;
 
 use crate::{blockchain::BlockchainError, Block, SyncBlock};
 
+/// The outcome a transaction's receipt commits to. Before
+/// [EIP-658](https://eips.ethereum.org/EIPS/eip-658) (Byzantium), a receipt committed to the
+/// post-transaction state root (openethereum's `eip98Transition`, née EIP-98); from Byzantium
+/// onwards it commits to a boolean status code instead. The scheme is a fork-wide switch, but
+/// the state root itself is per-transaction, so this is carried once per receipt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionOutcome {
+    /// Pre-Byzantium: the state root immediately after this transaction executed.
+    StateRoot(B256),
+    /// Post-Byzantium (EIP-658): the receipt already carries its own status code.
+    Status,
+}
+
 /// A locally mined block, which contains complete information.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct LocalBlock {
     header: block::Header,
     transactions: Vec<SignedTransaction>,
-    transaction_callers: Vec<Address>,
+    transaction_callers: OnceCell<Vec<Address>>,
     transaction_receipts: Vec<Arc<BlockReceipt>>,
+    receipts_root: B256,
+    receipt_outcomes: Vec<TransactionOutcome>,
     ommers: Vec<block::Header>,
     ommer_hashes: Vec<B256>,
     withdrawals: Option<Vec<Withdrawal>>,
     hash: B256,
 }
 
+impl PartialEq for LocalBlock {
+    fn eq(&self, other: &Self) -> bool {
+        // `transaction_callers` is intentionally excluded: it's a cache of a pure function of
+        // `transactions`, and comparing `OnceCell`'s `.get()` would make equality depend on
+        // whether that cache happened to be populated yet, rather than on block content.
+        self.header == other.header
+            && self.transactions == other.transactions
+            && self.transaction_receipts == other.transaction_receipts
+            && self.receipts_root == other.receipts_root
+            && self.receipt_outcomes == other.receipt_outcomes
+            && self.ommers == other.ommers
+            && self.ommer_hashes == other.ommer_hashes
+            && self.withdrawals == other.withdrawals
+            && self.hash == other.hash
+    }
+}
+
+impl Eq for LocalBlock {}
+
 impl LocalBlock {
     /// Constructs an empty block, i.e. no transactions.
     pub fn empty(partial_header: PartialHeader) -> Self {
@@ -36,19 +71,76 @@ impl LocalBlock {
             Vec::new(),
             Vec::new(),
             Vec::new(),
+            Vec::new(),
             None,
         )
     }
 
-    /// Constructs a new instance with the provided data.
+    /// Constructs a new instance with the provided data. `receipt_outcomes` gives, for each
+    /// transaction's receipt, whether it commits to a post-transaction state root (before
+    /// [EIP-658](https://eips.ethereum.org/EIPS/eip-658)) or a status code (from EIP-658
+    /// onwards); see [`TransactionOutcome`]. A `StateRoot` outcome replaces the receipt's
+    /// `data` with that state root; a `Status` outcome keeps the receipt's `data` as passed in.
+    /// It must have the same length as `transaction_receipts`.
     pub fn new(
-        mut partial_header: PartialHeader,
+        partial_header: PartialHeader,
         transactions: Vec<SignedTransaction>,
         transaction_callers: Vec<Address>,
         transaction_receipts: Vec<TransactionReceipt<Log>>,
+        receipt_outcomes: Vec<TransactionOutcome>,
+        ommers: Vec<Header>,
+        withdrawals: Option<Vec<Withdrawal>>,
+    ) -> Self {
+        Self::with_transaction_callers(
+            partial_header,
+            transactions,
+            OnceCell::from(transaction_callers),
+            transaction_receipts,
+            receipt_outcomes,
+            ommers,
+            withdrawals,
+        )
+    }
+
+    /// Constructs a new instance without eagerly recovering transaction senders. Callers are
+    /// lazily `ecrecover`ed on first access through [`LocalBlock::transaction_callers`] or
+    /// [`LocalBlock::detailed_transactions`], and the result is cached for subsequent calls.
+    /// This avoids redundant signature recovery during mining and block import when only the
+    /// header or receipts of a block are needed.
+    pub fn new_with_lazy_senders(
+        partial_header: PartialHeader,
+        transactions: Vec<SignedTransaction>,
+        transaction_receipts: Vec<TransactionReceipt<Log>>,
+        receipt_outcomes: Vec<TransactionOutcome>,
+        ommers: Vec<Header>,
+        withdrawals: Option<Vec<Withdrawal>>,
+    ) -> Self {
+        Self::with_transaction_callers(
+            partial_header,
+            transactions,
+            OnceCell::new(),
+            transaction_receipts,
+            receipt_outcomes,
+            ommers,
+            withdrawals,
+        )
+    }
+
+    fn with_transaction_callers(
+        mut partial_header: PartialHeader,
+        transactions: Vec<SignedTransaction>,
+        transaction_callers: OnceCell<Vec<Address>>,
+        transaction_receipts: Vec<TransactionReceipt<Log>>,
+        receipt_outcomes: Vec<TransactionOutcome>,
         ommers: Vec<Header>,
         withdrawals: Option<Vec<Withdrawal>>,
     ) -> Self {
+        assert_eq!(
+            transaction_receipts.len(),
+            receipt_outcomes.len(),
+            "a receipt outcome must be provided for every transaction receipt"
+        );
+
         let ommer_hashes = ommers.iter().map(Header::hash).collect::<Vec<_>>();
         let ommers_hash = keccak256(&rlp::encode_list(&ommers)[..]);
         let transactions_root =
@@ -63,14 +155,28 @@ impl LocalBlock {
         let header = Header::new(partial_header, ommers_hash, transactions_root);
 
         let hash = header.hash();
-        let transaction_receipts =
-            transaction_to_block_receipts(&hash, header.number, transaction_receipts);
+        let transaction_receipts = transaction_to_block_receipts(
+            &hash,
+            header.number,
+            transaction_receipts,
+            &receipt_outcomes,
+        );
+
+        // Derived from the same, already-converted `TypedReceipt` values that `receipt_proof`
+        // later walks, so the two can never drift apart.
+        let receipts_root = trie::ordered_trie_root(
+            transaction_receipts
+                .iter()
+                .map(|receipt| rlp::encode(&receipt.inner.inner).freeze()),
+        );
 
         Self {
             header,
             transactions,
             transaction_callers,
             transaction_receipts,
+            receipts_root,
+            receipt_outcomes,
             ommers,
             ommer_hashes,
             withdrawals,
@@ -83,11 +189,60 @@ impl LocalBlock {
         &self.transaction_receipts
     }
 
+    /// Returns the root of the trie of the block's transaction receipts, computed from the
+    /// receipts as stored in this block (after any [`TransactionOutcome::StateRoot`] override).
+    /// The header's own `receipts_root` is taken unchanged from the partial header passed to the
+    /// constructor; it is not set from, or checked against, this value.
+    pub fn receipts_root(&self) -> B256 {
+        self.receipts_root
+    }
+
+    /// Returns the [`TransactionOutcome`] each of this block's receipts was constructed with, in
+    /// transaction order. For [`TransactionOutcome::Status`], the receipt's `data` was kept as
+    /// passed in.
+    pub fn receipt_outcomes(&self) -> &[TransactionOutcome] {
+        &self.receipt_outcomes
+    }
+
+    /// Returns a Merkle Patricia proof that the receipt of the transaction at
+    /// `transaction_index` is included under [`LocalBlock::receipts_root`], as the ordered
+    /// list of RLP-encoded trie nodes on the path from the root to the leaf.
+    pub fn receipt_proof(&self, transaction_index: usize) -> Vec<Bytes> {
+        let trie = mpt::Trie::build(self.transaction_receipts.iter().enumerate().map(
+            |(index, receipt)| {
+                (
+                    rlp::encode(&index).freeze(),
+                    rlp::encode(&receipt.inner.inner).freeze(),
+                )
+            },
+        ));
+
+        trie.proof(&rlp::encode(&transaction_index).freeze())
+    }
+
+    /// Returns a Merkle Patricia proof that the RLP encoding of the transaction at `index` is
+    /// included under the header's `transactions_root`, as the ordered list of RLP-encoded
+    /// trie nodes on the path from the root to the leaf.
+    pub fn transaction_proof(&self, index: usize) -> Vec<Bytes> {
+        let trie = mpt::Trie::build(self.transactions.iter().enumerate().map(
+            |(index, transaction)| {
+                (
+                    rlp::encode(&index).freeze(),
+                    rlp::encode(transaction).freeze(),
+                )
+            },
+        ));
+
+        trie.proof(&rlp::encode(&index).freeze())
+    }
+
     /// Retrieves the block's transactions.
     pub fn detailed_transactions(&self) -> impl Iterator<Item = DetailedTransaction<'_>> {
+        let transaction_callers = self.transaction_callers();
+
         izip!(
             self.transactions.iter(),
-            self.transaction_callers.iter(),
+            transaction_callers.iter(),
             self.transaction_receipts.iter()
         )
         .map(|(transaction, caller, receipt)| {
@@ -96,6 +251,160 @@ impl LocalBlock {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `transactions` is left empty throughout: none of these tests need a real
+    // `SignedTransaction`, and its definition isn't available in this crate to safely
+    // construct one outside of decoding real signed input.
+
+    // All the non-`data` fields below are irrelevant to the behavior under test, so they're
+    // filled with their type's default rather than any particular value.
+    fn dummy_receipt(data: TypedReceiptData) -> TransactionReceipt<Log> {
+        TransactionReceipt {
+            inner: TypedReceipt {
+                cumulative_gas_used: Default::default(),
+                logs_bloom: Default::default(),
+                logs: Vec::new(),
+                data,
+            },
+            transaction_hash: Default::default(),
+            transaction_index: Default::default(),
+            from: Default::default(),
+            to: Default::default(),
+            contract_address: Default::default(),
+            gas_used: Default::default(),
+            effective_gas_price: Default::default(),
+        }
+    }
+
+    #[test]
+    fn state_root_outcome_overrides_receipt_data_and_matches_receipts_root() {
+        let correct_state_root = keccak256(b"post-transaction state");
+
+        // Deliberately wrong, to prove the `StateRoot` outcome below overrides it rather than
+        // passing the receipt's own `data` through unchanged.
+        let receipt = dummy_receipt(TypedReceiptData::PreEip658Legacy {
+            state_root: keccak256(b"stale"),
+        });
+
+        let block = LocalBlock::new(
+            PartialHeader::default(),
+            Vec::new(),
+            Vec::new(),
+            vec![receipt],
+            vec![TransactionOutcome::StateRoot(correct_state_root)],
+            Vec::new(),
+            None,
+        );
+
+        let receipts = block.transaction_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(
+            receipts[0].inner.inner.data,
+            TypedReceiptData::PreEip658Legacy {
+                state_root: correct_state_root
+            }
+        );
+
+        let expected_root = trie::ordered_trie_root(
+            receipts
+                .iter()
+                .map(|receipt| rlp::encode(&receipt.inner.inner).freeze()),
+        );
+        assert_eq!(block.receipts_root(), expected_root);
+    }
+
+    #[test]
+    fn transaction_callers_cache_is_excluded_from_equality() {
+        let block = LocalBlock::new_with_lazy_senders(
+            PartialHeader::default(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            None,
+        );
+        let other = block.clone();
+
+        // Force the lazy cache to populate on `block` only; `other`'s `OnceCell` stays empty.
+        let _ = block.transaction_callers();
+
+        assert_eq!(block, other);
+    }
+
+    #[test]
+    fn receipt_proof_matches_receipts_root() {
+        let receipts = (0u8..3)
+            .map(|index| {
+                dummy_receipt(TypedReceiptData::PreEip658Legacy {
+                    state_root: keccak256([index]),
+                })
+            })
+            .collect::<Vec<_>>();
+        let receipt_outcomes = receipts
+            .iter()
+            .map(|_| TransactionOutcome::Status)
+            .collect();
+
+        let block = LocalBlock::new(
+            PartialHeader::default(),
+            Vec::new(),
+            Vec::new(),
+            receipts,
+            receipt_outcomes,
+            Vec::new(),
+            None,
+        );
+
+        for index in 0..3 {
+            let proof = block.receipt_proof(index);
+            assert!(!proof.is_empty());
+            assert_eq!(keccak256(&proof[0]), block.receipts_root());
+        }
+    }
+
+    #[test]
+    fn block_receipts_response_matches_transaction_receipts() {
+        let block = LocalBlock::new(
+            PartialHeader::default(),
+            Vec::new(),
+            Vec::new(),
+            vec![dummy_receipt(TypedReceiptData::PreEip658Legacy {
+                state_root: Default::default(),
+            })],
+            vec![TransactionOutcome::Status],
+            Vec::new(),
+            None,
+        );
+
+        let response =
+            BlockReceipts::block_receipts_response(&block).expect("local blocks don't fail");
+        assert_eq!(response, block.transaction_receipts().to_vec());
+    }
+
+    #[test]
+    fn transaction_proof_is_empty_for_an_empty_block() {
+        // A real, non-empty `transaction_proof` needs an actual `SignedTransaction`, whose
+        // definition isn't available in this crate; the empty-trie sentinel is the one case
+        // that can be exercised here.
+        let block = LocalBlock::empty(PartialHeader::default());
+        assert!(block.transaction_proof(0).is_empty());
+    }
+}
+
+fn recover_transaction_callers(transactions: &[SignedTransaction]) -> Vec<Address> {
+    transactions
+        .iter()
+        .map(|transaction| {
+            transaction
+                .recover()
+                .expect("transactions in a local block are expected to have valid signatures")
+        })
+        .collect()
+}
+
 impl Block for LocalBlock {
     type Error = BlockchainError;
 
@@ -119,7 +428,8 @@ impl Block for LocalBlock {
     }
 
     fn transaction_callers(&self) -> &[Address] {
-        &self.transaction_callers
+        self.transaction_callers
+            .get_or_init(|| recover_transaction_callers(&self.transactions))
     }
 
     fn transaction_receipts(&self) -> Result<Vec<Arc<BlockReceipt>>, Self::Error> {
@@ -135,6 +445,20 @@ impl Block for LocalBlock {
     }
 }
 
+/// Blanket extension of [`Block`] with an `eth_getBlockReceipts`/`parity_getBlockReceipts`-style
+/// response, so code holding only `&dyn Block`/`Arc<dyn SyncBlock>` — i.e. the JSON-RPC
+/// dispatch path — can fetch every receipt of a block in one call, already decorated with the
+/// block hash, block number, cumulative gas, and globally-assigned log indices, without looking
+/// up each transaction's receipt individually.
+pub trait BlockReceipts: Block {
+    /// Returns every receipt of the block in a single, ordered response.
+    fn block_receipts_response(&self) -> Result<Vec<Arc<BlockReceipt>>, Self::Error> {
+        self.transaction_receipts()
+    }
+}
+
+impl<T: Block + ?Sized> BlockReceipts for T {}
+
 impl rlp::Encodable for LocalBlock {
     fn rlp_append(&self, s: &mut rlp::RlpStream) {
         let mut num_fields = 3;
@@ -158,15 +482,25 @@ fn transaction_to_block_receipts(
     block_hash: &B256,
     block_number: u64,
     receipts: Vec<TransactionReceipt<Log>>,
+    receipt_outcomes: &[TransactionOutcome],
 ) -> Vec<Arc<BlockReceipt>> {
     let mut log_index = 0;
 
-    receipts
-        .into_iter()
+    izip!(receipts, receipt_outcomes)
         .enumerate()
-        .map(|(transaction_index, receipt)| {
+        .map(|(transaction_index, (receipt, receipt_outcome))| {
             let transaction_index = transaction_index as u64;
 
+            // Pre-Byzantium, a receipt commits to the post-transaction state root instead of a
+            // status code (EIP-98/EIP-658); every other receipt shape already carries its own
+            // status, so only the state-root case needs to override what was passed in.
+            let data = match *receipt_outcome {
+                TransactionOutcome::StateRoot(state_root) => {
+                    TypedReceiptData::PreEip658Legacy { state_root }
+                }
+                TransactionOutcome::Status => receipt.inner.data,
+            };
+
             Arc::new(BlockReceipt {
                 inner: TransactionReceipt {
                     inner: TypedReceipt {
@@ -195,7 +529,7 @@ fn transaction_to_block_receipts(
                                 removed: false,
                             })
                             .collect(),
-                        data: receipt.inner.data,
+                        data,
                     },
                     transaction_hash: receipt.transaction_hash,
                     transaction_index,
@@ -216,4 +550,355 @@ impl From<LocalBlock> for Arc<dyn SyncBlock<Error = BlockchainError>> {
     fn from(value: LocalBlock) -> Self {
         Arc::new(value)
     }
-}
\ No newline at end of file
+}
+
+/// A minimal hex-nibble Merkle Patricia trie, materialized purely so callers can walk proof
+/// paths for the receipts and transactions tries (see [`LocalBlock::receipt_proof`] and
+/// [`LocalBlock::transaction_proof`]). `trie::ordered_trie_root` already gives a canonical root
+/// hash for a sequence of RLP values, but it doesn't expose the underlying nodes, so this module
+/// rebuilds the same trie and walks it to collect the RLP-encoded nodes on the path to a leaf.
+mod mpt {
+    use edr_eth::{Bytes, B256};
+    use revm::primitives::keccak256;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Node {
+        Leaf {
+            path: Vec<u8>,
+            value: Bytes,
+        },
+        Extension {
+            path: Vec<u8>,
+            child: Box<Node>,
+        },
+        Branch {
+            children: [Option<Box<Node>>; 16],
+            value: Option<Bytes>,
+        },
+    }
+
+    /// A trie materialized from a full set of key-value pairs, keyed by the raw (unhashed)
+    /// nibbles of each key.
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    pub struct Trie {
+        root: Option<Box<Node>>,
+    }
+
+    impl Trie {
+        /// Builds a trie from `entries`, keyed by the raw bytes of each pair's first element.
+        pub fn build(entries: impl Iterator<Item = (Bytes, Bytes)>) -> Self {
+            let mut trie = Self::default();
+            for (key, value) in entries {
+                trie.root = Some(insert(trie.root.take(), &to_nibbles(&key), value));
+            }
+            trie
+        }
+
+        /// Returns the ordered list of RLP-encoded nodes on the path from the root to the leaf
+        /// for `key`, or an empty proof if the trie has no entries. The first entry is always
+        /// the root, whose `keccak256` is the trie's root hash; a node whose encoding is
+        /// shorter than 32 bytes is embedded inline in its parent rather than referenced by
+        /// hash, so it is not pushed as a separate entry.
+        pub fn proof(&self, key: &[u8]) -> Vec<Bytes> {
+            let mut proof = Vec::new();
+            if let Some(root) = &self.root {
+                walk(root, &to_nibbles(key), &mut proof, true);
+            }
+            proof
+        }
+    }
+
+    fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    /// Hex-prefix encoding of a nibble path, per the Ethereum yellow paper (appendix C).
+    fn hex_prefix_encode(nibbles: &[u8], is_leaf: bool) -> Bytes {
+        let mut flag = if is_leaf { 2u8 } else { 0u8 };
+        let mut nibbles = nibbles;
+        let mut out = Vec::with_capacity(nibbles.len() / 2 + 1);
+
+        if nibbles.len() % 2 == 1 {
+            flag += 1;
+            out.push((flag << 4) | nibbles[0]);
+            nibbles = &nibbles[1..];
+        } else {
+            out.push(flag << 4);
+        }
+
+        for pair in nibbles.chunks_exact(2) {
+            out.push((pair[0] << 4) | pair[1]);
+        }
+
+        out.into()
+    }
+
+    fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+        a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+    }
+
+    fn insert(node: Option<Box<Node>>, path: &[u8], value: Bytes) -> Box<Node> {
+        match node {
+            None => Box::new(Node::Leaf {
+                path: path.to_vec(),
+                value,
+            }),
+            Some(node) => match *node {
+                Node::Leaf {
+                    path: existing,
+                    value: existing_value,
+                } => insert_into_leaf(existing, existing_value, path, value),
+                Node::Extension {
+                    path: existing,
+                    child,
+                } => insert_into_extension(existing, child, path, value),
+                Node::Branch {
+                    mut children,
+                    value: branch_value,
+                } => {
+                    if path.is_empty() {
+                        Box::new(Node::Branch {
+                            children,
+                            value: Some(value),
+                        })
+                    } else {
+                        let index = path[0] as usize;
+                        children[index] = Some(insert(children[index].take(), &path[1..], value));
+                        Box::new(Node::Branch {
+                            children,
+                            value: branch_value,
+                        })
+                    }
+                }
+            },
+        }
+    }
+
+    fn insert_into_leaf(
+        existing_path: Vec<u8>,
+        existing_value: Bytes,
+        new_path: &[u8],
+        new_value: Bytes,
+    ) -> Box<Node> {
+        let common = common_prefix_len(&existing_path, new_path);
+
+        if common == existing_path.len() && common == new_path.len() {
+            return Box::new(Node::Leaf {
+                path: new_path.to_vec(),
+                value: new_value,
+            });
+        }
+
+        let mut children: [Option<Box<Node>>; 16] = Default::default();
+        let mut branch_value = None;
+
+        if common == existing_path.len() {
+            branch_value = Some(existing_value);
+        } else {
+            children[existing_path[common] as usize] = Some(Box::new(Node::Leaf {
+                path: existing_path[common + 1..].to_vec(),
+                value: existing_value,
+            }));
+        }
+
+        if common == new_path.len() {
+            branch_value = Some(new_value);
+        } else {
+            children[new_path[common] as usize] = Some(Box::new(Node::Leaf {
+                path: new_path[common + 1..].to_vec(),
+                value: new_value,
+            }));
+        }
+
+        wrap_in_extension(
+            &new_path[..common],
+            Box::new(Node::Branch {
+                children,
+                value: branch_value,
+            }),
+        )
+    }
+
+    fn insert_into_extension(
+        existing_path: Vec<u8>,
+        child: Box<Node>,
+        new_path: &[u8],
+        new_value: Bytes,
+    ) -> Box<Node> {
+        let common = common_prefix_len(&existing_path, new_path);
+
+        if common == existing_path.len() {
+            let grown_child = insert(Some(child), &new_path[common..], new_value);
+            return Box::new(Node::Extension {
+                path: existing_path,
+                child: grown_child,
+            });
+        }
+
+        let mut children: [Option<Box<Node>>; 16] = Default::default();
+        let remaining = existing_path.len() - common;
+        children[existing_path[common] as usize] = Some(if remaining == 1 {
+            child
+        } else {
+            Box::new(Node::Extension {
+                path: existing_path[common + 1..].to_vec(),
+                child,
+            })
+        });
+
+        let mut branch_value = None;
+        if common == new_path.len() {
+            branch_value = Some(new_value);
+        } else {
+            children[new_path[common] as usize] = Some(Box::new(Node::Leaf {
+                path: new_path[common + 1..].to_vec(),
+                value: new_value,
+            }));
+        }
+
+        wrap_in_extension(
+            &new_path[..common],
+            Box::new(Node::Branch {
+                children,
+                value: branch_value,
+            }),
+        )
+    }
+
+    fn wrap_in_extension(shared_path: &[u8], branch: Box<Node>) -> Box<Node> {
+        if shared_path.is_empty() {
+            branch
+        } else {
+            Box::new(Node::Extension {
+                path: shared_path.to_vec(),
+                child: branch,
+            })
+        }
+    }
+
+    fn encode_node(node: &Node) -> Bytes {
+        let mut stream = rlp::RlpStream::new();
+        match node {
+            Node::Leaf { path, value } => {
+                stream.begin_list(2);
+                stream.append(&hex_prefix_encode(path, true)[..]);
+                stream.append(&value[..]);
+            }
+            Node::Extension { path, child } => {
+                stream.begin_list(2);
+                stream.append(&hex_prefix_encode(path, false)[..]);
+                append_child(&mut stream, child);
+            }
+            Node::Branch { children, value } => {
+                stream.begin_list(17);
+                for child in children {
+                    match child {
+                        Some(child) => append_child(&mut stream, child),
+                        None => {
+                            stream.append_empty_data();
+                        }
+                    }
+                }
+                match value {
+                    Some(value) => {
+                        stream.append(&value[..]);
+                    }
+                    None => {
+                        stream.append_empty_data();
+                    }
+                }
+            }
+        }
+        stream.out().freeze()
+    }
+
+    fn append_child(stream: &mut rlp::RlpStream, child: &Node) {
+        let encoded = encode_node(child);
+        if encoded.len() < 32 {
+            stream.append_raw(&encoded, 1);
+        } else {
+            stream.append(&keccak256(&encoded));
+        }
+    }
+
+    fn walk(node: &Node, path: &[u8], proof: &mut Vec<Bytes>, push: bool) {
+        if push {
+            proof.push(encode_node(node));
+        }
+
+        match node {
+            Node::Leaf { .. } => {}
+            Node::Extension {
+                path: extension_path,
+                child,
+            } => {
+                if path.len() >= extension_path.len()
+                    && path[..extension_path.len()] == extension_path[..]
+                {
+                    let child_encoded = encode_node(child);
+                    walk(
+                        child,
+                        &path[extension_path.len()..],
+                        proof,
+                        child_encoded.len() >= 32,
+                    );
+                }
+            }
+            Node::Branch { children, .. } => {
+                if let Some((&index, rest)) = path.split_first() {
+                    if let Some(child) = &children[index as usize] {
+                        let child_encoded = encode_node(child);
+                        walk(child, rest, proof, child_encoded.len() >= 32);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Exercises the invariant `receipt_proof`/`transaction_proof` rely on: the first entry
+        // of a `Trie::proof` is always the root node, so its `keccak256` must equal the root
+        // hash the rest of the codebase computes via `edr_eth::trie::ordered_trie_root` over the
+        // same entries. A mismatch here is exactly the bug that let `receipts_root` and
+        // `receipt_proof` drift apart.
+        fn entries(count: usize) -> Vec<(Bytes, Bytes)> {
+            (0..count)
+                .map(|index| {
+                    (
+                        rlp::encode(&index).freeze(),
+                        rlp::encode(&format!("value-{index}")).freeze(),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn proof_root_matches_ordered_trie_root() {
+            for count in [0usize, 1, 2, 16, 17, 256] {
+                let entries = entries(count);
+                let trie = Trie::build(entries.clone().into_iter());
+
+                let expected_root = edr_eth::trie::ordered_trie_root(
+                    entries.iter().map(|(_, value)| value.clone()),
+                );
+
+                if count == 0 {
+                    assert!(trie.proof(&rlp::encode(&0usize).freeze()).is_empty());
+                    continue;
+                }
+
+                for index in 0..count {
+                    let proof = trie.proof(&rlp::encode(&index).freeze());
+                    assert!(!proof.is_empty());
+                    assert_eq!(keccak256(&proof[0]), expected_root);
+                }
+            }
+        }
+    }
+}